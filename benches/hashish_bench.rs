@@ -0,0 +1,183 @@
+//! Criterion benchmarks for `hashish`'s probing behavior and the 1BRC
+//! aggregation pipeline it backs. `hashish` and `aggregate` are modules of
+//! the `bologna` binary crate rather than a published library, so they're
+//! pulled in by path instead of through a `use bologna::...` dependency.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rustc_hash::FxHashMap;
+
+#[path = "../src/hashish.rs"]
+mod hashish;
+#[path = "../src/aggregate.rs"]
+mod aggregate;
+
+use aggregate::{aggregate_chunk, parse_temperature};
+
+/// City names drawn from a small fixed pool so cardinality (and therefore
+/// collision pressure) can be varied independently of line count and name
+/// length. `max_name_len` only pads names out to a variable target length;
+/// it never truncates the `City{i:05}` prefix, since cutting into the
+/// numeric suffix would collapse distinct `i`s onto the same string.
+fn city_pool(cardinality: usize, max_name_len: usize) -> Vec<String> {
+    (0..cardinality)
+        .map(|i| {
+            let target_len = 4 + i % max_name_len.saturating_sub(3).max(1);
+            let mut name = format!("City{i:05}");
+            while name.len() < target_len {
+                name.push('x');
+            }
+            name
+        })
+        .collect()
+}
+
+fn synthetic_measurements(cardinality: usize, max_name_len: usize, lines: usize) -> String {
+    let cities = city_pool(cardinality, max_name_len);
+    let mut out = String::with_capacity(lines * 20);
+
+    for i in 0..lines {
+        let city = &cities[i % cities.len()];
+        let whole = (i % 100) as i32 - 50;
+        let frac = (i * 7) % 10;
+
+        out.push_str(city);
+        out.push(';');
+        if whole < 0 {
+            out.push('-');
+        }
+        out.push_str(&whole.unsigned_abs().to_string());
+        out.push('.');
+        out.push_str(&frac.to_string());
+        out.push('\n');
+    }
+
+    out
+}
+
+fn bench_aggregate_chunk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aggregate_chunk");
+    for &cardinality in &[100usize, 1_000, 10_000] {
+        let data = synthetic_measurements(cardinality, 24, 200_000);
+        let bytes = data.as_bytes();
+
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(cardinality), bytes, |b, bytes| {
+            b.iter(|| black_box(aggregate_chunk(black_box(bytes))))
+        });
+    }
+    group.finish();
+}
+
+/// Reports the probe-length histogram and load factor for a range of
+/// cardinalities (untimed, since `Stats` is a diagnostic, not the thing
+/// being measured), then times straight-line insertion at each cardinality.
+fn bench_probe_behavior(c: &mut Criterion) {
+    let mut group = c.benchmark_group("probe_behavior");
+    for &cardinality in &[1_000usize, 10_000, 100_000] {
+        let cities = city_pool(cardinality, 24);
+
+        let mut probe = hashish::Table::<16384, &str, i32>::new();
+        for city in &cities {
+            *probe.emplace(city.as_str()) += 1;
+        }
+        let stats = probe.stats();
+        let total_ops: usize = stats.probe_histogram.iter().sum();
+        let avg_probe_len = stats
+            .probe_histogram
+            .iter()
+            .enumerate()
+            .map(|(bucket, &count)| (bucket + 1) * count)
+            .sum::<usize>() as f64
+            / total_ops.max(1) as f64;
+        let collisions_per_million =
+            stats.collisions as f64 / total_ops.max(1) as f64 * 1_000_000.0;
+
+        eprintln!(
+            "cardinality={cardinality} load_factor={:.3} avg_probe_len={avg_probe_len:.2} \
+             collisions_per_million={collisions_per_million:.0}",
+            stats.load_factor(),
+        );
+
+        group.throughput(Throughput::Elements(cardinality as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(cardinality),
+            &cities,
+            |b, cities| {
+                b.iter(|| {
+                    let mut table = hashish::Table::<16384, &str, i32>::new();
+                    for (i, city) in cities.iter().enumerate() {
+                        *table.emplace(city.as_str()) += i as i32;
+                    }
+                    black_box(table.collision_count())
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Compares straight-line insertion against `FxHashMap` at a fixed
+/// cardinality, to tell whether the triangular-probe SwissTable layout is
+/// earning its keep over the generic standard-library map.
+fn bench_vs_fxhashmap(c: &mut Criterion) {
+    let cardinality = 10_000;
+    let cities = city_pool(cardinality, 24);
+
+    let mut group = c.benchmark_group("vs_fxhashmap");
+    group.throughput(Throughput::Elements(cardinality as u64));
+
+    group.bench_function("hashish::Table", |b| {
+        b.iter(|| {
+            let mut table = hashish::Table::<16384, &str, i32>::new();
+            for (i, city) in cities.iter().enumerate() {
+                *table.emplace(city.as_str()) += i as i32;
+            }
+            black_box(table.collision_count())
+        })
+    });
+    group.bench_function("FxHashMap", |b| {
+        b.iter(|| {
+            let mut map: FxHashMap<&str, i32> = FxHashMap::default();
+            for (i, city) in cities.iter().enumerate() {
+                *map.entry(city.as_str()).or_insert(0) += i as i32;
+            }
+            black_box(map.len())
+        })
+    });
+    group.finish();
+}
+
+fn bench_parse_temperature(c: &mut Criterion) {
+    let samples: Vec<String> = (0..10_000)
+        .map(|i: i32| {
+            let whole = (i % 100) - 50;
+            let frac = (i * 3) % 10;
+            // `parse_temperature` assumes a `\n` and more file content follow
+            // the fractional digit, as every real measurement line has; pad
+            // so standalone samples don't run past the end of the slice.
+            format!(
+                "{}{}.{frac}\n0000",
+                if whole < 0 { "-" } else { "" },
+                whole.abs(),
+            )
+        })
+        .collect();
+    let images: Vec<&[u8]> = samples.iter().map(|s| s.as_bytes()).collect();
+
+    c.bench_function("parse_temperature", |b| {
+        b.iter(|| {
+            for image in &images {
+                black_box(parse_temperature(black_box(image)));
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_aggregate_chunk,
+    bench_probe_behavior,
+    bench_vs_fxhashmap,
+    bench_parse_temperature,
+);
+criterion_main!(benches);
@@ -0,0 +1,215 @@
+//! The 1BRC aggregation pipeline. Split out of `main.rs` so `benches/` can
+//! exercise `aggregate_chunk` and `parse_temperature` directly instead of
+//! only through the end-to-end `measurements.txt` path.
+
+use std::{
+    fmt, str,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::hashish;
+
+pub(crate) type StatTable<'a> = hashish::Table<14813, &'a str, Stat>;
+
+#[derive(Debug, Default)]
+pub(crate) struct StatChunk<'a> {
+    data: StatTable<'a>,
+}
+
+impl<'a> StatChunk<'a> {
+    #[inline]
+    pub(crate) fn merge_with(&mut self, StatChunk { data }: StatChunk<'a>) {
+        for (city, stat) in data.iter() {
+            self.data.emplace(&city).merge_with(&stat)
+        }
+    }
+}
+
+impl<'a> fmt::Display for StatChunk<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries = self.data.iter().collect::<Vec<_>>();
+        entries.sort_by_cached_key(|(key, _)| *key);
+
+        write!(f, "{{")?;
+        for (city, stat) in entries {
+            write!(f, "{city}={stat},")?;
+        }
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Stat {
+    min: f32,
+    sum: f32,
+    count: u32,
+    max: f32,
+}
+
+impl Stat {
+    const DEFAULT_INSTANCE: Self = Self {
+        min: f32::MAX,
+        sum: 0.0,
+        count: 0,
+        max: f32::MIN,
+    };
+
+    #[inline]
+    fn add(&mut self, x: f32) {
+        self.min = if self.min < x { self.min } else { x };
+        self.sum += x;
+        self.count += 1;
+        self.max = if self.max > x { self.max } else { x };
+    }
+
+    #[inline]
+    fn merge_with(&mut self, rhs: &Self) {
+        self.min = f32::min(self.min, rhs.min);
+        self.sum += rhs.sum;
+        self.count += rhs.count;
+        self.max = f32::max(self.max, rhs.max);
+    }
+
+    fn average(&self) -> f32 {
+        self.sum / (self.count as f32)
+    }
+}
+
+impl Default for Stat {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT_INSTANCE
+    }
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:.1}/{}", self.min, self.average(), self.max)
+    }
+}
+
+#[inline]
+pub(crate) fn aggregate_chunk<'a>(chunk: &'a [u8]) -> StatChunk<'a> {
+    let mut stat_map = StatTable::new();
+    let mut cursor = chunk;
+
+    loop {
+        let mut city_pos = 3;
+        while city_pos < cursor.len() && cursor[city_pos] != b';' {
+            city_pos += 1
+        }
+
+        if city_pos < cursor.len() {
+            let city = unsafe { str::from_utf8_unchecked(&cursor[..city_pos]) };
+            let (temperature, remains) = parse_temperature(&cursor[(city_pos + 1)..]);
+            stat_map.emplace(city).add(temperature);
+            cursor = remains;
+        } else {
+            break StatChunk { data: stat_map };
+        }
+    }
+}
+
+/// Splits `extent` into `count` newline-aligned segments. Each segment's end
+/// is found by scanning forward from a `chunk_size`-sized stride to the next
+/// `\n`, so a segment can only end exactly on a `\n` byte — except the very
+/// last one, which always runs to `extent_size` so the segments together
+/// cover all of `extent`, including a final line without a `\n`. Segments
+/// can be empty when `extent` has fewer lines than `count`.
+pub(crate) fn chunkify<'a>(extent: &'a [u8], count: usize) -> Vec<&'a [u8]> {
+    let mut chunks = Vec::with_capacity(count);
+    let extent_size = extent.len();
+    let chunk_size = extent_size / count;
+    let mut base = 0;
+    let mut offset = chunk_size;
+
+    for i in 0..count {
+        while offset < extent_size && extent[offset] != b'\n' {
+            offset += 1;
+        }
+
+        let end = if offset < extent_size && i + 1 < count {
+            offset + 1
+        } else {
+            extent_size
+        };
+        chunks.push(&extent[base..end]);
+        base = end;
+        offset += usize::min(chunk_size, extent_size - base);
+    }
+
+    chunks
+}
+
+/// Segments carved per worker, independent of how many workers there are.
+/// Carving more, smaller segments than there are threads is what lets a
+/// thread that finishes early steal further segments instead of idling.
+const SEGMENTS_PER_WORKER: usize = 16;
+
+/// Aggregates `extent` across `worker_count` scoped threads. Segments are
+/// still newline-aligned the way `chunkify` always produced them, but there
+/// are many more of them than threads, and each thread claims its next one
+/// from a shared atomic cursor instead of owning a fixed range up front —
+/// so a thread that lands on a run of short lines keeps stealing work
+/// instead of finishing early and idling while a skewed sibling catches up.
+pub(crate) fn aggregate_parallel<'a>(extent: &'a [u8], worker_count: usize) -> StatChunk<'a> {
+    let segments = chunkify(extent, worker_count * SEGMENTS_PER_WORKER);
+    let next_segment = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        (0..worker_count)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut local = StatChunk::default();
+                    loop {
+                        let index = next_segment.fetch_add(1, Ordering::Relaxed);
+                        let Some(&segment) = segments.get(index) else {
+                            break;
+                        };
+                        local.merge_with(aggregate_chunk(segment));
+                    }
+                    local
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .reduce(|mut p, q| {
+                p.merge_with(q);
+                p
+            })
+            .unwrap()
+    })
+}
+
+#[inline]
+pub(crate) fn parse_temperature<'a>(image: &'a [u8]) -> (f32, &'a [u8]) {
+    let mut float;
+    let neg = image[0] == b'-';
+
+    let index = if neg {
+        float = (image[1] - b'0') as f32;
+        2
+    } else {
+        float = (image[0] - b'0') as f32;
+        1
+    };
+
+    let remains = if image[index] == b'.' {
+        float += (image[index + 1] - b'0') as f32 / 10.0;
+        &image[(index + 3)..]
+    } else {
+        float = 10.0 * float + (image[index] - b'0') as f32;
+        float += (image[index + 2] - b'0') as f32 / 10.0;
+        &image[(index + 4)..]
+    };
+
+    if neg {
+        (-float, remains)
+    } else {
+        (float, remains)
+    }
+}
@@ -1,75 +1,513 @@
-use std::{
-    borrow, fmt,
-    hash::{Hash, Hasher},
+//! Open-addressing hash table with SwissTable-style control bytes.
+//!
+//! `core`-only without the `std`/`alloc` features: [`BorrowedTable`] runs the same probing logic
+//! over storage the caller already owns, so it needs no allocator. [`Table`]
+//! is the convenience layer on top — it owns a growable `Vec` and is gated
+//! behind the `alloc` feature (on by default wherever `std` is, since `std`
+//! implies `alloc`). Without `alloc`, key and value types also aren't
+//! required to implement `Debug` (see [`MaybeDebug`]), so a minimal
+//! embedded type isn't forced to derive it just to sit in a
+//! [`BorrowedTable`].
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use core::{
+    borrow,
+    hash::{BuildHasher, Hash, Hasher},
 };
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+/// Slots per control group. Matches the width of a 128-bit SWAR compare.
+const GROUP: usize = 16;
+
+/// Marks a slot that has never held an entry.
+const EMPTY: u8 = 0xFF;
+
+/// Marks a slot whose entry was removed (not produced by any op yet, but
+/// reserved so a future `remove` can tombstone without breaking probe chains).
+#[allow(dead_code)]
+const DELETED: u8 = 0x80;
+
+/// Low 7 bits of a hash, with the top bit forced to zero so that tag bytes
+/// never collide with `EMPTY`/`DELETED`.
+#[inline]
+fn h2(hash: usize) -> u8 {
+    (hash & 0x7F) as u8
+}
+
+/// Selects the starting group for a key.
+#[inline]
+fn h1(hash: usize) -> usize {
+    hash >> 7
+}
+
+#[inline]
+fn repeat(byte: u8) -> u128 {
+    u128::from_ne_bytes([byte; GROUP])
+}
+
+/// SWAR broadcast-compare: returns a bitmask with bit `i` set when lane `i`
+/// of `group` equals `byte`. The borrow of the subtraction can also set the
+/// bit of a lane holding `byte ^ 1` in a run directly above a matching lane,
+/// so the lowest set bit is always a true match but higher ones may not be;
+/// `find_or_probe` confirms tag candidates by comparing keys.
+#[inline]
+fn match_byte_mask(group: u128, byte: u8) -> u16 {
+    const LSB: u128 = u128::from_ne_bytes([0x01; GROUP]);
+    const MSB: u128 = u128::from_ne_bytes([0x80; GROUP]);
+
+    let xored = group ^ repeat(byte);
+    let matched = xored.wrapping_sub(LSB) & !xored & MSB;
+
+    let mut mask = 0u16;
+    for lane in 0..GROUP {
+        if (matched >> (lane * 8)) & 0x80 != 0 {
+            mask |= 1 << lane;
+        }
+    }
+    mask
+}
+
+enum Slot {
+    Match(usize),
+    Vacant(usize),
+}
+
+/// Buckets for the probe-length histogram in [`Stats`]: bucket `i` counts
+/// `insert`/`emplace` calls that scanned `i + 1` groups before landing on a
+/// match or a vacant slot, with the last bucket catching everything at or
+/// beyond that depth.
+const PROBE_HISTOGRAM_BUCKETS: usize = 8;
+
+#[inline]
+fn record_probe(histogram: &mut [usize; PROBE_HISTOGRAM_BUCKETS], groups_scanned: usize) {
+    let bucket = (groups_scanned - 1).min(PROBE_HISTOGRAM_BUCKETS - 1);
+    histogram[bucket] += 1;
+}
 
-#[derive(Debug)]
-pub struct Table<const N: usize, A, B>
+/// Snapshot of a table's occupancy and probing behavior, for benchmarks and
+/// for tuning a chosen capacity `N`. A fixed-size summary rather than a heap
+/// allocation, so it is available on both `Table` and `BorrowedTable`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Stats {
+    pub len: usize,
+    pub capacity: usize,
+    pub collisions: usize,
+    pub probe_histogram: [usize; PROBE_HISTOGRAM_BUCKETS],
+}
+
+impl Stats {
+    /// Fraction of capacity currently occupied.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.capacity as f64
+    }
+}
+
+/// Load factor (as a fraction of capacity) past which an owned `Table`
+/// doubles and rehashes rather than let probe chains keep growing.
+/// `BorrowedTable` cannot grow and has no equivalent.
+#[cfg(feature = "alloc")]
+const MAX_LOAD_NUMERATOR: usize = 7;
+#[cfg(feature = "alloc")]
+const MAX_LOAD_DENOMINATOR: usize = 8;
+
+/// Stands in for `fmt::Debug` everywhere `Entry`/`BorrowedTable` bound their
+/// key and value types, so a truly minimal `no_std`, non-`alloc` key or
+/// value type isn't forced to implement `Debug` just to sit in a table it
+/// never formats. Under `alloc` (and therefore `std`) this is exactly
+/// `fmt::Debug`; without it, every type satisfies it for free.
+#[cfg(feature = "alloc")]
+pub trait MaybeDebug: fmt::Debug {}
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> MaybeDebug for T {}
+
+#[cfg(not(feature = "alloc"))]
+pub trait MaybeDebug {}
+#[cfg(not(feature = "alloc"))]
+impl<T> MaybeDebug for T {}
+
+#[derive(Copy, Clone)]
+pub struct Entry<A, B>
 where
-    A: Hashed + PartialEq + fmt::Debug,
-    B: Default + fmt::Debug,
+    A: PartialEq + MaybeDebug,
+    B: Default + MaybeDebug,
 {
-    store: [Entry<A, B>; N],
-    collisions: usize,
+    key: Option<A>,
+    value: B,
 }
 
-#[derive(Copy, Clone, Debug)]
-struct Entry<A, B>
+#[cfg(feature = "alloc")]
+impl<A, B> fmt::Debug for Entry<A, B>
 where
-    A: Hashed + PartialEq + fmt::Debug,
+    A: PartialEq + fmt::Debug,
     B: Default + fmt::Debug,
 {
-    key: Option<A>,
-    value: B,
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", &self.key)
+            .field("value", &self.value)
+            .finish()
+    }
 }
 
-impl<const N: usize, A, B> Default for Table<N, A, B> 
+impl<A, B> Entry<A, B>
 where
-    A: Hashed + PartialEq + fmt::Debug + Copy,
-    B: Default + fmt::Debug + Copy,
+    A: PartialEq + MaybeDebug,
+    B: Default + MaybeDebug,
 {
-    fn default() -> Self {
-        Table::new()
+    /// An unoccupied slot, for callers building their own backing storage
+    /// for [`BorrowedTable::new`].
+    pub fn empty() -> Self {
+        Self {
+            key: None,
+            value: Default::default(),
+        }
     }
 }
 
 impl<A, B> Default for Entry<A, B>
 where
-    A: Hashed + PartialEq + fmt::Debug,
-    B: Default + fmt::Debug,
+    A: PartialEq + MaybeDebug,
+    B: Default + MaybeDebug,
 {
     fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Rounds `cap` up to the smallest `GROUP`-aligned size whose group count
+/// (`cap / GROUP`) is a power of two. The triangular probe sequence in
+/// [`find_or_probe`] (`+1, +3, +6, ...` groups from the start, i.e. the
+/// `k`-th probe lands `k * (k + 1) / 2` groups past it, mod `group_count`)
+/// only visits every group before
+/// repeating when `group_count` is a power of two — for any other modulus
+/// it can cycle through a strict subset of groups forever. `Table::new`,
+/// `Table::grow`, and `BorrowedTable::new` all go through this so that
+/// invariant holds for as long as the table exists.
+#[inline]
+fn round_up_to_pow2_groups(cap: usize) -> usize {
+    cap.div_ceil(GROUP).next_power_of_two() * GROUP
+}
+
+/// Scans control groups in triangular probe sequence (`+1, +3, +6, ...`
+/// groups) until `matches` accepts a tagged slot or an `EMPTY` slot is
+/// found. Both `Table` and `BorrowedTable` route every access method
+/// through here (and through it alone while `Table` rehashes into a freshly
+/// grown store) so there is exactly one probing routine to get right.
+///
+/// Relies on `control.len() / GROUP` being a power of two (see
+/// [`round_up_to_pow2_groups`]) for the triangular sequence below to reach
+/// every group rather than cycling through a subset of them forever.
+fn find_or_probe<A, B>(
+    store: &[Entry<A, B>],
+    control: &[u8],
+    hash: usize,
+    matches: impl Fn(&A) -> bool,
+) -> (Slot, usize)
+where
+    A: PartialEq + MaybeDebug,
+    B: Default + MaybeDebug,
+{
+    let cap = control.len();
+    let group_count = cap.div_ceil(GROUP);
+    let tag = h2(hash);
+    let mut group_index = h1(hash) % group_count;
+    let mut stride = 1;
+    let mut groups_scanned = 0;
+
+    loop {
+        groups_scanned += 1;
+        let base = group_index * GROUP;
+        let len = GROUP.min(cap - base);
+
+        let mut bytes = [EMPTY; GROUP];
+        bytes[..len].copy_from_slice(&control[base..base + len]);
+        let group = u128::from_ne_bytes(bytes);
+
+        let mut candidates = match_byte_mask(group, tag);
+        while candidates != 0 {
+            let lane = candidates.trailing_zeros() as usize;
+            candidates &= candidates - 1;
+
+            let index = base + lane;
+            let key = store[index].key.as_ref().expect("tagged slot is occupied");
+            if matches(key) {
+                return (Slot::Match(index), groups_scanned);
+            }
+        }
+
+        let empties = match_byte_mask(group, EMPTY);
+        if empties != 0 {
+            let lane = empties.trailing_zeros() as usize;
+            return (Slot::Vacant(base + lane), groups_scanned);
+        }
+
+        group_index = (group_index + stride) % group_count;
+        stride += 1;
+    }
+}
+
+/// A fixed-capacity table over storage the caller already owns — no
+/// allocator required. Capacity is `entries.len()` and is fixed for the
+/// table's lifetime: unlike [`Table`], there is no heap to reallocate into,
+/// so it is the caller's responsibility to size the storage above their
+/// expected entry count.
+pub struct BorrowedTable<'a, A, B, S = rustc_hash::FxBuildHasher>
+where
+    A: PartialEq + MaybeDebug,
+    B: Default + MaybeDebug,
+{
+    store: &'a mut [Entry<A, B>],
+    control: &'a mut [u8],
+    build_hasher: S,
+    len: usize,
+    collisions: usize,
+    probe_histogram: [usize; PROBE_HISTOGRAM_BUCKETS],
+}
+
+impl<'a, A, B, S> BorrowedTable<'a, A, B, S>
+where
+    A: Copy + PartialEq + MaybeDebug + Hash,
+    B: Default + Copy + MaybeDebug,
+    S: BuildHasher + Default,
+{
+    /// `entries` and `control` must have equal length, and that length must
+    /// be a power-of-two number of `GROUP`-sized groups (see
+    /// [`round_up_to_pow2_groups`]) — unlike `Table`, `BorrowedTable` cannot
+    /// round the caller's storage up for them, so callers must size it
+    /// accordingly up front.
+    pub fn new(entries: &'a mut [Entry<A, B>], control: &'a mut [u8]) -> Self {
+        assert_eq!(
+            entries.len(),
+            control.len(),
+            "entries and control must have equal length"
+        );
+        assert_eq!(
+            control.len(),
+            round_up_to_pow2_groups(control.len()),
+            "control length must be a power-of-two number of {GROUP}-slot groups"
+        );
+        entries.fill(Entry::empty());
+        control.fill(EMPTY);
+
         Self {
-            key: None,
-            value: Default::default(),
+            store: entries,
+            control,
+            build_hasher: S::default(),
+            len: 0,
+            collisions: 0,
+            probe_histogram: [0; PROBE_HISTOGRAM_BUCKETS],
         }
     }
+
+    pub fn insert(&mut self, key: A, value: B) {
+        let hash = hash_key(&self.build_hasher, &key);
+        let (slot, groups_scanned) = find_or_probe(self.store, self.control, hash, |k| *k == key);
+        self.collisions += groups_scanned - 1;
+        record_probe(&mut self.probe_histogram, groups_scanned);
+
+        match slot {
+            Slot::Match(index) => self.store[index].value = value,
+            Slot::Vacant(index) => {
+                self.control[index] = h2(hash);
+                self.store[index].key = Some(key);
+                self.store[index].value = value;
+                self.len += 1;
+            }
+        }
+    }
+
+    pub fn get<K>(&self, key: &K) -> Option<&B>
+    where
+        A: borrow::Borrow<K>,
+        K: Hash + Eq + ?Sized,
+    {
+        let hash = hash_key(&self.build_hasher, key);
+        match find_or_probe(self.store, self.control, hash, |k| k.borrow() == key).0 {
+            Slot::Match(index) => Some(&self.store[index].value),
+            Slot::Vacant(_) => None,
+        }
+    }
+
+    pub fn get_mut<K>(&mut self, key: &K) -> Option<&mut B>
+    where
+        A: borrow::Borrow<K>,
+        K: Hash + Eq + ?Sized,
+    {
+        let hash = hash_key(&self.build_hasher, key);
+        match find_or_probe(self.store, self.control, hash, |k| k.borrow() == key).0 {
+            Slot::Match(index) => Some(&mut self.store[index].value),
+            Slot::Vacant(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn emplace(&mut self, key: A) -> &mut B {
+        let hash = hash_key(&self.build_hasher, &key);
+        let (slot, groups_scanned) = find_or_probe(self.store, self.control, hash, |k| *k == key);
+        self.collisions += groups_scanned - 1;
+        record_probe(&mut self.probe_histogram, groups_scanned);
+
+        let index = match slot {
+            Slot::Match(index) => index,
+            Slot::Vacant(index) => {
+                self.control[index] = h2(hash);
+                self.store[index].key = Some(key);
+                self.len += 1;
+                index
+            }
+        };
+        &mut self.store[index].value
+    }
+
+    pub fn collision_count(&self) -> usize {
+        self.collisions
+    }
+
+    /// Snapshot of occupancy and probe depth, for tuning the caller-chosen
+    /// capacity.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            len: self.len,
+            capacity: self.control.len(),
+            collisions: self.collisions,
+            probe_histogram: self.probe_histogram,
+        }
+    }
+}
+
+/// Hashes any borrowed form of `A` through a `BuildHasher`, the same way
+/// `std::collections::HashMap` does.
+#[allow(clippy::manual_hash_one)] // equivalent to hash_one, spelled out for clarity
+fn hash_key<K: Hash + ?Sized, S: BuildHasher>(build_hasher: &S, key: &K) -> usize {
+    let mut hasher = build_hasher.build_hasher();
+    key.hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+#[cfg(feature = "alloc")]
+pub struct Table<const N: usize, A, B, S = rustc_hash::FxBuildHasher>
+where
+    A: PartialEq + fmt::Debug,
+    B: Default + fmt::Debug,
+{
+    store: Vec<Entry<A, B>>,
+    control: Vec<u8>,
+    build_hasher: S,
+    len: usize,
+    collisions: usize,
+    probe_histogram: [usize; PROBE_HISTOGRAM_BUCKETS],
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, A, B, S> fmt::Debug for Table<N, A, B, S>
+where
+    A: PartialEq + fmt::Debug,
+    B: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("store", &self.store)
+            .field("len", &self.len)
+            .field("collisions", &self.collisions)
+            .field("probe_histogram", &self.probe_histogram)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, A, B, S> Default for Table<N, A, B, S>
+where
+    A: PartialEq + fmt::Debug + Copy + Hash,
+    B: Default + fmt::Debug + Copy,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Table::new()
+    }
 }
 
-impl<const N: usize, A, B> Table<N, A, B>
+#[cfg(feature = "alloc")]
+impl<const N: usize, A, B, S> Table<N, A, B, S>
 where
-    A: Hashed + Copy + PartialEq + fmt::Debug,
+    A: Copy + PartialEq + fmt::Debug + Hash,
     B: Default + Copy + fmt::Debug,
+    S: BuildHasher + Default,
 {
+    /// An owning table that starts at capacity `N` (rounded up, if needed,
+    /// to the next power-of-two number of groups — see
+    /// [`round_up_to_pow2_groups`]) and doubles (rehashing every entry)
+    /// once it crosses the load factor.
     pub fn new() -> Self {
+        let cap = round_up_to_pow2_groups(N);
         Self {
-            store: [Entry::default(); N],
+            store: vec![Entry::empty(); cap],
+            control: vec![EMPTY; cap],
+            build_hasher: S::default(),
+            len: 0,
             collisions: 0,
+            probe_histogram: [0; PROBE_HISTOGRAM_BUCKETS],
+        }
+    }
+
+    /// Doubles the backing store and rehashes every occupied entry into it.
+    /// Called from `insert`/`emplace` once `len + 1` would cross the load
+    /// factor, before it is known whether the key is already present.
+    ///
+    /// Routes the doubled size through [`round_up_to_pow2_groups`] rather
+    /// than trusting that doubling a power-of-two group count always stays
+    /// one (true today, but `new`'s rounding is what actually establishes
+    /// the invariant `find_or_probe` depends on, so `grow` re-derives it
+    /// instead of assuming it).
+    fn grow(&mut self) {
+        let new_cap = round_up_to_pow2_groups(self.control.len() * 2);
+        let mut new_store = vec![Entry::empty(); new_cap];
+        let mut new_control = vec![EMPTY; new_cap];
+
+        for entry in &self.store {
+            let Some(key) = entry.key else { continue };
+            let hash = hash_key(&self.build_hasher, &key);
+            let (slot, _) = find_or_probe(&new_store, &new_control, hash, |_| false);
+            let Slot::Vacant(index) = slot else {
+                unreachable!("a freshly rehashed table cannot already contain the key")
+            };
+            new_control[index] = h2(hash);
+            new_store[index] = Entry {
+                key: Some(key),
+                value: entry.value,
+            };
+        }
+
+        self.store = new_store;
+        self.control = new_control;
+    }
+
+    fn maybe_grow(&mut self) {
+        if (self.len + 1) * MAX_LOAD_DENOMINATOR > self.control.len() * MAX_LOAD_NUMERATOR {
+            self.grow();
         }
     }
 
     pub fn insert(&mut self, key: A, value: B) {
-        let hash = key.compute_hash();
-        let mut index = hash % N;
-        loop {
-            let e = &mut self.store[index];
-            if e.key.is_some_and(|k| k != key) {
-                // Fine better functions
-                index = (index + hash.reverse_bits()) % N;
-            } else {
-                e.key = Some(key);
-                e.value = value;
-                break;
+        self.maybe_grow();
+
+        let hash = hash_key(&self.build_hasher, &key);
+        let (slot, groups_scanned) = find_or_probe(&self.store, &self.control, hash, |k| *k == key);
+        self.collisions += groups_scanned - 1;
+        record_probe(&mut self.probe_histogram, groups_scanned);
+
+        match slot {
+            Slot::Match(index) => self.store[index].value = value,
+            Slot::Vacant(index) => {
+                self.control[index] = h2(hash);
+                self.store[index].key = Some(key);
+                self.store[index].value = value;
+                self.len += 1;
             }
         }
     }
@@ -77,69 +515,64 @@ where
     pub fn get<K>(&self, key: &K) -> Option<&B>
     where
         A: borrow::Borrow<K>,
-        K: Hashed + Eq + fmt::Debug,
+        K: Hash + Eq + ?Sized,
     {
-        let hash = key.compute_hash();
-        let mut index = hash % N;
-        loop {
-            let e = &self.store[index];
-            if let Some(k) = e.key {
-                if k.borrow() == key {
-                    break Some(&e.value);
-                } else {
-                    break None;
-                }
-            } else {
-                index = (index + hash.reverse_bits()) % N;
-             }
+        let hash = hash_key(&self.build_hasher, key);
+        match find_or_probe(&self.store, &self.control, hash, |k| k.borrow() == key).0 {
+            Slot::Match(index) => Some(&self.store[index].value),
+            Slot::Vacant(_) => None,
         }
     }
 
     pub fn get_mut<K>(&mut self, key: &K) -> Option<&mut B>
     where
         A: borrow::Borrow<K>,
-        K: Hashed + Eq,
+        K: Hash + Eq + ?Sized,
     {
-        let hash = key.compute_hash();
-        let mut index = hash % N;
-        loop {
-            let e = &self.store[index];
-            if let Some(k) = e.key {
-                if k.borrow() == key {
-                    break Some(&mut self.store[index].value);
-                } else {
-                    index = (index + hash.reverse_bits()) % N;
-                }
-            } else {
-                break None;
-            }
+        let hash = hash_key(&self.build_hasher, key);
+        match find_or_probe(&self.store, &self.control, hash, |k| k.borrow() == key).0 {
+            Slot::Match(index) => Some(&mut self.store[index].value),
+            Slot::Vacant(_) => None,
         }
     }
 
     #[inline]
     pub fn emplace(&mut self, key: A) -> &mut B {
-        let hash = key.compute_hash();
-        let mut index = hash % N;
-        loop {
-            if let Some(k) = &self.store[index].key {
-                if k == &key {
-                    break &mut self.store[index].value;
-                } else {
-                    self.collisions += 1;
-                    index = (index + hash.reverse_bits()) % N;
-                }
-            } else {
+        self.maybe_grow();
+
+        let hash = hash_key(&self.build_hasher, &key);
+        let (slot, groups_scanned) = find_or_probe(&self.store, &self.control, hash, |k| *k == key);
+        self.collisions += groups_scanned - 1;
+        record_probe(&mut self.probe_histogram, groups_scanned);
+
+        let index = match slot {
+            Slot::Match(index) => index,
+            Slot::Vacant(index) => {
+                self.control[index] = h2(hash);
                 self.store[index].key = Some(key);
-                break &mut self.store[index].value;
+                self.len += 1;
+                index
             }
-        }
+        };
+        &mut self.store[index].value
     }
 
     pub fn collision_count(&self) -> usize {
         self.collisions
     }
 
-    pub fn iter(&self) -> TableIterator<N, A, B> {
+    /// Snapshot of occupancy and probe depth, for benchmarks and for tuning
+    /// the chosen `N`.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            len: self.len,
+            capacity: self.control.len(),
+            collisions: self.collisions,
+            probe_histogram: self.probe_histogram,
+        }
+    }
+
+    pub fn iter(&self) -> TableIterator<'_, N, A, B, S> {
         TableIterator {
             inner: self,
             index: 0,
@@ -147,19 +580,23 @@ where
     }
 }
 
-pub struct TableIterator<'a, const N: usize, A, B>
+#[cfg(feature = "alloc")]
+pub struct TableIterator<'a, const N: usize, A, B, S>
 where
-    A: Hashed + Copy + PartialEq + fmt::Debug,
+    A: Copy + PartialEq + fmt::Debug + Hash,
     B: Default + Copy + fmt::Debug,
+    S: BuildHasher + Default,
 {
-    inner: &'a Table<N, A, B>,
+    inner: &'a Table<N, A, B, S>,
     index: usize,
 }
 
-impl<'a, const N: usize, A, B> Iterator for TableIterator<'a, N, A, B>
+#[cfg(feature = "alloc")]
+impl<'a, const N: usize, A, B, S> Iterator for TableIterator<'a, N, A, B, S>
 where
-    A: Hashed + Copy + PartialEq + fmt::Debug,
+    A: Copy + PartialEq + fmt::Debug + Hash,
     B: Default + Copy + fmt::Debug,
+    S: BuildHasher + Default,
 {
     type Item = (A, B);
 
@@ -181,24 +618,13 @@ where
     }
 }
 
-pub trait Hashed {
-    fn compute_hash(&self) -> usize;
-}
-
-impl<'a> Hashed for &'a str {
-    #[inline]
-    fn compute_hash(&self) -> usize {
-        let mut hasher = rustc_hash::FxHasher::default();
-        self.hash(&mut hasher);
-        hasher.finish() as usize
-    }
-}
-
 #[cfg(test)]
 mod test {
     #[test]
     fn testies() {
-        let mut h = super::Table::<419, &str, i32>::new();
+        // 432 rounds up to 512 (32 groups of 16), a power-of-two group
+        // count so the triangular probe sequence covers every group.
+        let mut h = super::Table::<432, &str, i32>::new();
         println!(" Wut? ");
         h.insert("Paudrigue Anderzorn", 46);
         h.insert("Sanna Japp", 38);
@@ -219,7 +645,39 @@ mod test {
         assert_eq!(Some(&39), h.get(&"Sanna Japp"));
 
         for (key, value) in h.iter() {
-            println!("{key} {value}"); 
+            println!("{key} {value}");
         }
     }
+
+    #[test]
+    fn works_with_random_state() {
+        let mut h = super::Table::<64, &str, i32, std::hash::RandomState>::new();
+        h.insert("alpha", 1);
+        h.insert("beta", 2);
+        assert_eq!(Some(&1), h.get(&"alpha"));
+        assert_eq!(Some(&2), h.get(&"beta"));
+    }
+
+    #[test]
+    fn every_key_survives_repeated_grow() {
+        let mut h = super::Table::<16, u32, u32>::new();
+        for i in 0..5_000u32 {
+            h.insert(i, i * 2);
+        }
+        for i in 0..5_000u32 {
+            assert_eq!(Some(&(i * 2)), h.get(&i));
+        }
+    }
+
+    #[test]
+    fn borrowed_table_over_caller_storage() {
+        let mut entries = [super::Entry::empty(); 64];
+        let mut control = [0u8; 64];
+        let mut h = super::BorrowedTable::<&str, i32>::new(&mut entries, &mut control);
+
+        h.insert("alpha", 1);
+        h.insert("beta", 2);
+        assert_eq!(Some(&1), h.get(&"alpha"));
+        assert_eq!(Some(&2), h.get(&"beta"));
+    }
 }